@@ -143,6 +143,8 @@ pub mod windows {}
 pub mod solaris;
 #[cfg(target_os = "solid_asp3")]
 pub mod solid;
+#[cfg(target_os = "theseus")]
+pub mod theseus;
 #[cfg(target_os = "vxworks")]
 pub mod vxworks;
 
@@ -0,0 +1,91 @@
+//! Theseus-specific extensions to general I/O primitives.
+
+use crate::io;
+use crate::sys;
+
+#[cfg(test)]
+mod tests;
+
+/// A handle to the Theseus kernel log.
+///
+/// Bytes written through this handle go straight to the kernel's log output,
+/// regardless of where the current task's stdio streams are redirected or
+/// captured. Output that isn't valid UTF-8 is logged lossily.
+///
+/// A UTF-8 sequence split across two writes is logged once the rest of it
+/// has been written. If the handle is dropped before that, the incomplete
+/// sequence is logged lossily.
+///
+/// Created by the [`kernel_log`] function.
+#[derive(Debug)]
+pub struct KernelLog {
+    // The start of a UTF-8 sequence that was cut off at the end of the last write.
+    partial: Vec<u8>,
+}
+
+/// Constructs a new handle to the Theseus kernel log.
+pub const fn kernel_log() -> KernelLog {
+    KernelLog { partial: Vec::new() }
+}
+
+// Returns the index at which an incomplete UTF-8 sequence at the end of `buf` starts, or
+// `buf.len()` if it doesn't end with one. Only the last three bytes need to be checked, as no
+// sequence is longer than four.
+fn incomplete_tail_start(buf: &[u8]) -> usize {
+    for i in 1..=buf.len().min(3) {
+        let width = match buf[buf.len() - i] {
+            0x80..=0xBF => continue,
+            0xC2..=0xDF => 2,
+            0xE0..=0xEF => 3,
+            0xF0..=0xF4 => 4,
+            _ => 0,
+        };
+        return if width > i { buf.len() - i } else { buf.len() };
+    }
+    buf.len()
+}
+
+impl KernelLog {
+    // Logs the complete UTF-8 sequences of `buf` through `log`, holding back an incomplete one at
+    // the end until the next write.
+    fn write_to(
+        &mut self,
+        buf: &[u8],
+        log: impl FnOnce(&str) -> io::Result<()>,
+    ) -> io::Result<usize> {
+        let joined;
+        let data = if self.partial.is_empty() {
+            buf
+        } else {
+            joined = [&self.partial[..], buf].concat();
+            &joined[..]
+        };
+
+        let (complete, tail) = data.split_at(incomplete_tail_start(data));
+        if !complete.is_empty() {
+            log(&String::from_utf8_lossy(complete))?;
+        }
+        self.partial = tail.to_vec();
+        Ok(buf.len())
+    }
+}
+
+impl io::Write for KernelLog {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.write_to(buf, sys::stdio::write_kernel_log)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        // The kernel log isn't buffered on our side. An incomplete UTF-8 sequence is held back
+        // rather than flushed, since the rest of it may still be written.
+        Ok(())
+    }
+}
+
+impl Drop for KernelLog {
+    fn drop(&mut self) {
+        if !self.partial.is_empty() {
+            let _ = sys::stdio::write_kernel_log(&String::from_utf8_lossy(&self.partial));
+        }
+    }
+}
@@ -0,0 +1,47 @@
+use super::{incomplete_tail_start, kernel_log};
+use crate::io::Write;
+
+#[test]
+fn incomplete_tail() {
+    let cases: [(&[u8], usize); 10] = [
+        (b"", 0),
+        (b"abc", 3),
+        // A lead byte missing some or all of its continuation bytes is held back.
+        (b"ab\xE2", 2),
+        (b"ab\xE2\x82", 2),
+        (b"\xF0\x9F\x98", 0),
+        // Complete sequences are logged as they are.
+        (b"ab\xE2\x82\xAC", 5),
+        (b"\xF0\x9F\x98\x80", 4),
+        // Bytes that can't start a sequence are left to the lossy conversion.
+        (b"ab\xC0", 3),
+        (b"ab\xF5", 3),
+        (b"\x82\x82\x82", 3),
+    ];
+    for (buf, start) in cases {
+        assert_eq!(incomplete_tail_start(buf), start, "{buf:?}");
+    }
+}
+
+#[test]
+fn split_sequence_is_logged_once_complete() {
+    let mut log = kernel_log();
+    let mut lines = Vec::new();
+    for chunk in [&b"price: \xE2"[..], b"\x82", b"\xAC5\n"] {
+        let written = log
+            .write_to(chunk, |s| {
+                lines.push(s.to_owned());
+                Ok(())
+            })
+            .unwrap();
+        assert_eq!(written, chunk.len());
+    }
+    assert_eq!(lines, ["price: ", "\u{20AC}5\n"]);
+}
+
+#[test]
+fn write_line_reaches_kernel_log() {
+    let mut log = kernel_log();
+    writeln!(log, "kernel log test line").unwrap();
+    log.flush().unwrap();
+}
@@ -0,0 +1,6 @@
+//! Theseus-specific definitions.
+
+#![unstable(feature = "theseus_ext", issue = "none")]
+
+pub mod io;
+pub mod thread;
@@ -0,0 +1,101 @@
+//! Theseus-specific extensions to primitives in the [`std::thread`] module.
+//!
+//! [`std::thread`]: crate::thread
+
+use crate::fmt;
+use crate::io;
+use crate::marker::PhantomData;
+use crate::num::NonZeroUsize;
+use crate::sys;
+use crate::sys_common::AsInner;
+use crate::thread;
+
+/// Returns the number of CPUs the current task is allowed to run on.
+///
+/// Unlike [`available_parallelism`], which reports every online CPU, this
+/// takes the task's CPU pinning into account. A task pinned to a core can
+/// only ever run on that core, so this returns 1 for it.
+///
+/// [`available_parallelism`]: crate::thread::available_parallelism
+pub fn affinity_parallelism() -> io::Result<NonZeroUsize> {
+    sys::thread::affinity_parallelism()
+}
+
+/// Returns the ID of the CPU that the current task is running on.
+///
+/// This is only a snapshot: unless the task is pinned to a core, the
+/// scheduler may migrate it to another CPU at any point after this returns.
+///
+/// This currently always returns `Some`, as the kernel can always tell which
+/// CPU it is running on. The `Option` leaves room for that to change.
+pub fn current_cpu() -> Option<usize> {
+    sys::thread::current_cpu()
+}
+
+/// A guard that keeps the current task from being preempted while it's alive.
+///
+/// Preemption is re-enabled when the guard is dropped. The guard must be
+/// dropped on the task that created it, so it is neither `Send` nor `Sync`.
+///
+/// While the guard is held, the scheduler can't switch away from the current
+/// task on this CPU, so the critical section should be as short as possible.
+/// In particular, it must not block (e.g. by locking a contended mutex,
+/// sleeping, joining a thread, or doing I/O), as the task that would unblock
+/// it may never get to run. Allocating should also be avoided, since the
+/// allocator may need to take locks.
+#[must_use = "if unused, preemption will immediately be re-enabled"]
+pub struct NoPreemptGuard {
+    _inner: sys::thread::NoPreemptGuard,
+    _not_send: PhantomData<*const ()>,
+}
+
+impl NoPreemptGuard {
+    /// Disables preemption for the current task until the returned guard is
+    /// dropped.
+    pub fn new() -> NoPreemptGuard {
+        NoPreemptGuard { _inner: sys::thread::hold_preemption(), _not_send: PhantomData }
+    }
+}
+
+impl fmt::Debug for NoPreemptGuard {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("NoPreemptGuard").finish_non_exhaustive()
+    }
+}
+
+/// Theseus-specific extensions to [`JoinHandle`].
+///
+/// [`JoinHandle`]: crate::thread::JoinHandle
+pub trait JoinHandleExt {
+    /// Returns `true` once the underlying task has exited.
+    ///
+    /// This does not block and does not reap the task, so the handle can
+    /// still be joined afterwards. Unlike [`JoinHandle::is_finished`], which
+    /// becomes `true` as soon as the thread's closure has returned, this
+    /// only becomes `true` once the kernel has marked the task as exited.
+    ///
+    /// [`JoinHandle::is_finished`]: crate::thread::JoinHandle::is_finished
+    fn has_exited(&self) -> bool;
+}
+
+impl<T> JoinHandleExt for thread::JoinHandle<T> {
+    fn has_exited(&self) -> bool {
+        self.as_inner().has_exited()
+    }
+}
+
+/// Registers a callback to run when the current thread exits.
+///
+/// Callbacks run after the thread's closure has returned or panicked, as
+/// part of tearing down the thread's thread-local storage, and before the
+/// underlying task exits. They run in the reverse order of registration.
+/// A callback that panics does not prevent the remaining callbacks from
+/// running.
+///
+/// # Panics
+///
+/// Panics if called while the current thread's thread-local storage is
+/// being destroyed, e.g. from within another exit callback.
+pub fn at_thread_exit<F: FnOnce() + 'static>(f: F) {
+    sys::thread::at_exit(Box::new(f))
+}
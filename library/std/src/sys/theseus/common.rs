@@ -10,6 +10,9 @@ pub unsafe fn init(_argc: isize, _argv: *const *const u8) {}
 
 // SAFETY: must be called only once during runtime cleanup.
 // NOTE: this is not guaranteed to run, for example when the program aborts.
+// NOTE: the main task's thread-local destructors only run once the task exits, which is after
+// this returns. Nothing they might use (e.g. the task's stdio streams or environment) may be torn
+// down here; that happens when the kernel cleans up the task.
 pub unsafe fn cleanup() {}
 
 pub fn unsupported<T>() -> std_io::Result<T> {
@@ -23,8 +26,8 @@ pub fn unsupported_err() -> std_io::Error {
     )
 }
 
-pub fn decode_error_kind(_code: i32) -> crate::io::ErrorKind {
-    crate::io::ErrorKind::Uncategorized
+pub fn decode_error_kind(code: i32) -> std_io::ErrorKind {
+    super::error_kind(code).unwrap_or(std_io::ErrorKind::Uncategorized)
 }
 
 pub fn abort_internal() -> ! {
@@ -1,3 +1,6 @@
+#[cfg(test)]
+mod tests;
+
 use crate::ffi::OsString;
 use crate::fmt;
 use crate::hash::{Hash, Hasher};
@@ -15,7 +18,14 @@
 pub struct DirEntry(!);
 
 #[derive(Clone, Debug)]
-pub struct OpenOptions {}
+pub struct OpenOptions {
+    read: bool,
+    write: bool,
+    append: bool,
+    truncate: bool,
+    create: bool,
+    create_new: bool,
+}
 
 pub struct FilePermissions(!);
 
@@ -162,19 +172,70 @@ pub fn file_type(&self) -> io::Result<FileType> {
 
 impl OpenOptions {
     pub fn new() -> OpenOptions {
-        OpenOptions {}
+        OpenOptions {
+            read: false,
+            write: false,
+            append: false,
+            truncate: false,
+            create: false,
+            create_new: false,
+        }
+    }
+
+    pub fn read(&mut self, read: bool) {
+        self.read = read;
+    }
+    pub fn write(&mut self, write: bool) {
+        self.write = write;
+    }
+    pub fn append(&mut self, append: bool) {
+        self.append = append;
+    }
+    pub fn truncate(&mut self, truncate: bool) {
+        self.truncate = truncate;
+    }
+    pub fn create(&mut self, create: bool) {
+        self.create = create;
+    }
+    pub fn create_new(&mut self, create_new: bool) {
+        self.create_new = create_new;
+    }
+
+    // Rejects the same flag combinations as the Unix implementation.
+    fn validate(&self) -> io::Result<()> {
+        if !self.read && !self.write && !self.append {
+            return Err(io::const_io_error!(
+                io::ErrorKind::InvalidInput,
+                "file must be opened with read, write, or append access",
+            ));
+        }
+
+        match (self.write, self.append) {
+            (true, false) => {}
+            (false, false) => {
+                if self.truncate || self.create || self.create_new {
+                    return Err(io::const_io_error!(
+                        io::ErrorKind::InvalidInput,
+                        "creating or truncating a file requires write or append access",
+                    ));
+                }
+            }
+            (_, true) => {
+                if self.truncate && !self.create_new {
+                    return Err(io::const_io_error!(
+                        io::ErrorKind::InvalidInput,
+                        "a file opened for appending can't be truncated",
+                    ));
+                }
+            }
+        }
+        Ok(())
     }
-
-    pub fn read(&mut self, _read: bool) {}
-    pub fn write(&mut self, _write: bool) {}
-    pub fn append(&mut self, _append: bool) {}
-    pub fn truncate(&mut self, _truncate: bool) {}
-    pub fn create(&mut self, _create: bool) {}
-    pub fn create_new(&mut self, _create_new: bool) {}
 }
 
 impl File {
-    pub fn open(_path: &Path, _opts: &OpenOptions) -> io::Result<File> {
+    pub fn open(_path: &Path, opts: &OpenOptions) -> io::Result<File> {
+        opts.validate()?;
         unsupported()
     }
 
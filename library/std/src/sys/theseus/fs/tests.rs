@@ -0,0 +1,53 @@
+use super::OpenOptions;
+use crate::io;
+
+// (read, write, append, truncate, create, create_new)
+type Flags = (bool, bool, bool, bool, bool, bool);
+
+fn options((read, write, append, truncate, create, create_new): Flags) -> OpenOptions {
+    let mut opts = OpenOptions::new();
+    opts.read(read);
+    opts.write(write);
+    opts.append(append);
+    opts.truncate(truncate);
+    opts.create(create);
+    opts.create_new(create_new);
+    opts
+}
+
+#[test]
+fn validate_rejects_invalid_combinations() {
+    let cases: [Flags; 8] = [
+        // No access at all.
+        (false, false, false, false, false, false),
+        (false, false, false, true, true, true),
+        // Creating or truncating without write or append access.
+        (true, false, false, false, true, false),
+        (true, false, false, false, false, true),
+        (true, false, false, true, false, false),
+        // Truncating a file opened for appending, unless it's newly created.
+        (false, false, true, true, false, false),
+        (false, true, true, true, false, false),
+        (false, false, true, true, true, false),
+    ];
+    for flags in cases {
+        let err = options(flags).validate().unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput, "{flags:?}");
+    }
+}
+
+#[test]
+fn validate_accepts_valid_combinations() {
+    let cases: [Flags; 7] = [
+        (true, false, false, false, false, false),
+        (false, true, false, false, false, false),
+        (false, false, true, false, false, false),
+        (false, true, false, true, true, false),
+        (false, true, false, false, false, true),
+        (false, false, true, false, true, false),
+        (false, false, true, true, false, true),
+    ];
+    for flags in cases {
+        assert!(options(flags).validate().is_ok(), "{flags:?}");
+    }
+}
@@ -1,3 +1,6 @@
+#[cfg(test)]
+mod tests;
+
 mod condvar;
 mod mutex;
 mod rwlock;
@@ -1,20 +1,25 @@
-use crate::cell::Cell;
+use super::super::current_task_id;
+use crate::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use libtheseus::task;
+
+// Stored in `owner` while no task holds the lock.
+const NO_OWNER: usize = usize::MAX;
 
 pub struct Mutex {
-    // This platform has no threads, so we can use a Cell here.
-    locked: Cell<bool>,
+    locked: AtomicBool,
+    // The ID of the task holding the lock. This is only used to detect a task trying to acquire
+    // the lock again, so relaxed accesses are enough: a task only ever sees its own ID here if
+    // it stored it itself.
+    owner: AtomicUsize,
 }
 
 pub type MovableMutex = Mutex;
 
-unsafe impl Send for Mutex {}
-unsafe impl Sync for Mutex {} // no threads on this platform
-
 impl Mutex {
     #[inline]
     #[rustc_const_stable(feature = "const_locks", since = "1.63.0")]
     pub const fn new() -> Mutex {
-        Mutex { locked: Cell::new(false) }
+        Mutex { locked: AtomicBool::new(false), owner: AtomicUsize::new(NO_OWNER) }
     }
 
     #[inline]
@@ -22,16 +27,35 @@ pub unsafe fn init(&mut self) {}
 
     #[inline]
     pub unsafe fn lock(&self) {
-        assert_eq!(self.locked.replace(true), false, "cannot recursively acquire mutex");
+        if unsafe { self.try_lock() } {
+            return;
+        }
+        // The current task is only needed to detect recursion, so it isn't looked up until the
+        // lock turns out to be held.
+        let current = current_task_id().unwrap_or(NO_OWNER);
+        while !unsafe { self.try_lock() } {
+            if current != NO_OWNER && self.owner.load(Ordering::Relaxed) == current {
+                panic!("cannot recursively acquire mutex");
+            }
+            // Yield rather than spin so that the task holding the lock gets a chance to run and
+            // release it, even on a single core.
+            task::yield_now();
+        }
     }
 
     #[inline]
     pub unsafe fn unlock(&self) {
-        self.locked.set(false);
+        self.owner.store(NO_OWNER, Ordering::Relaxed);
+        self.locked.store(false, Ordering::Release);
     }
 
     #[inline]
     pub unsafe fn try_lock(&self) -> bool {
-        self.locked.replace(true) == false
+        if self.locked.compare_exchange(false, true, Ordering::Acquire, Ordering::Relaxed).is_ok() {
+            self.owner.store(current_task_id().unwrap_or(NO_OWNER), Ordering::Relaxed);
+            true
+        } else {
+            false
+        }
     }
 }
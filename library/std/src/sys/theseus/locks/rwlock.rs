@@ -1,54 +1,61 @@
-use crate::cell::Cell;
+use super::super::current_task_id;
+use crate::sync::atomic::{AtomicIsize, AtomicUsize, Ordering};
+use libtheseus::task;
+
+// Stored in `writer` while no task holds the lock for writing.
+const NO_WRITER: usize = usize::MAX;
 
 pub struct RwLock {
-    // This platform has no threads, so we can use a Cell here.
-    mode: Cell<isize>,
+    // The number of readers, or -1 while locked for writing.
+    mode: AtomicIsize,
+    // The ID of the task holding the lock for writing, used to detect a task that would wait on
+    // itself. As with `Mutex::owner`, relaxed accesses are enough for that.
+    writer: AtomicUsize,
 }
 
 pub type MovableRwLock = RwLock;
 
-unsafe impl Send for RwLock {}
-unsafe impl Sync for RwLock {} // no threads on this platform
-
 impl RwLock {
     #[inline]
     #[rustc_const_stable(feature = "const_locks", since = "1.63.0")]
     pub const fn new() -> RwLock {
-        RwLock { mode: Cell::new(0) }
+        RwLock { mode: AtomicIsize::new(0), writer: AtomicUsize::new(NO_WRITER) }
     }
 
     #[inline]
     pub unsafe fn read(&self) {
-        let m = self.mode.get();
-        if m >= 0 {
-            self.mode.set(m + 1);
-        } else {
-            rtabort!("rwlock locked for writing");
+        while !unsafe { self.try_read() } {
+            self.check_not_writer();
+            task::yield_now();
         }
     }
 
     #[inline]
     pub unsafe fn try_read(&self) -> bool {
-        let m = self.mode.get();
-        if m >= 0 {
-            self.mode.set(m + 1);
-            true
-        } else {
-            false
+        let mut m = self.mode.load(Ordering::Relaxed);
+        while m >= 0 {
+            match self.mode.compare_exchange_weak(m, m + 1, Ordering::Acquire, Ordering::Relaxed) {
+                Ok(_) => return true,
+                Err(new) => m = new,
+            }
         }
+        false
     }
 
+    // FIXME: a task that holds a read lock and tries to write waits forever, as readers aren't
+    // tracked.
     #[inline]
     pub unsafe fn write(&self) {
-        if self.mode.replace(-1) != 0 {
-            rtabort!("rwlock locked for reading")
+        while !unsafe { self.try_write() } {
+            self.check_not_writer();
+            task::yield_now();
         }
     }
 
     #[inline]
     pub unsafe fn try_write(&self) -> bool {
-        if self.mode.get() == 0 {
-            self.mode.set(-1);
+        if self.mode.compare_exchange(0, -1, Ordering::Acquire, Ordering::Relaxed).is_ok() {
+            self.writer.store(current_task_id().unwrap_or(NO_WRITER), Ordering::Relaxed);
             true
         } else {
             false
@@ -57,11 +64,20 @@ pub unsafe fn try_write(&self) -> bool {
 
     #[inline]
     pub unsafe fn read_unlock(&self) {
-        self.mode.set(self.mode.get() - 1);
+        self.mode.fetch_sub(1, Ordering::Release);
     }
 
     #[inline]
     pub unsafe fn write_unlock(&self) {
-        assert_eq!(self.mode.replace(0), -1);
+        self.writer.store(NO_WRITER, Ordering::Relaxed);
+        assert_eq!(self.mode.swap(0, Ordering::Release), -1);
+    }
+
+    // Aborts if the current task holds the write lock, as waiting for it would never finish.
+    fn check_not_writer(&self) {
+        let current = current_task_id().unwrap_or(NO_WRITER);
+        if current != NO_WRITER && self.writer.load(Ordering::Relaxed) == current {
+            rtabort!("rwlock locked for writing");
+        }
     }
 }
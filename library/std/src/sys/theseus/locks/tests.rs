@@ -0,0 +1,56 @@
+use super::Mutex;
+use crate::cell::Cell;
+use crate::pin::Pin;
+use crate::sync::Arc;
+use crate::sys_common::remutex::ReentrantMutex;
+use crate::thread;
+use crate::time::Duration;
+
+#[test]
+fn try_lock_while_held() {
+    let m = Mutex::new();
+    unsafe {
+        m.lock();
+        assert!(!m.try_lock());
+        m.unlock();
+        assert!(m.try_lock());
+        assert!(!m.try_lock());
+        m.unlock();
+    }
+}
+
+#[test]
+#[should_panic(expected = "cannot recursively acquire mutex")]
+fn recursive_lock_panics() {
+    let m = Mutex::new();
+    unsafe {
+        m.lock();
+        m.lock();
+    }
+}
+
+#[test]
+fn reentrant_mutex_waits_for_both_unlocks() {
+    let m = unsafe {
+        // FIXME: Simplify this if Arc gets an Arc::get_pin_mut.
+        let mut m = Arc::new(ReentrantMutex::new(Cell::new(0)));
+        Pin::new_unchecked(Arc::get_mut_unchecked(&mut m)).init();
+        Pin::new_unchecked(m)
+    };
+    let m2 = m.clone();
+
+    let outer = m.as_ref().lock();
+    let inner = m.as_ref().lock();
+    let child = thread::spawn(move || {
+        let lock = m2.as_ref().lock();
+        assert_eq!(lock.get(), 2);
+    });
+
+    inner.set(1);
+    drop(inner);
+    // The child must still be waiting, as the outer lock is held.
+    thread::sleep(Duration::from_millis(50));
+    outer.set(2);
+    drop(outer);
+    child.join().unwrap();
+}
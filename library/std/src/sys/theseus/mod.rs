@@ -1,5 +1,8 @@
 #![deny(unsafe_op_in_unsafe_fn)]
 
+#[cfg(test)]
+mod tests;
+
 pub use libtheseus as _;
 
 #[no_mangle]
@@ -16,43 +19,76 @@
     main(argc, argv, crate::ptr::null())
 }
 
+// This allocates, so only use it for messages that aren't known at compile time. Fixed messages
+// should use `const_io_error!` instead.
 fn io_err(s: &str) -> crate::io::Error {
     crate::io::Error::new(crate::io::ErrorKind::Other, s)
 }
 
 fn current_task_id() -> crate::io::Result<usize> {
-    libtheseus::task::get_my_current_task_id().ok_or_else(|| io_err("couldn't get current task id"))
+    libtheseus::task::get_my_current_task_id().ok_or_else(|| {
+        crate::io::const_io_error!(crate::io::ErrorKind::Other, "couldn't get current task id")
+    })
 }
 
 fn current_task() -> crate::io::Result<&'static libtheseus::task::TaskRef> {
-    libtheseus::task::get_my_current_task().ok_or_else(|| io_err("couldn't get current task"))
+    libtheseus::task::get_my_current_task().ok_or_else(|| {
+        crate::io::const_io_error!(crate::io::ErrorKind::Other, "couldn't get current task")
+    })
+}
+
+// Theseus errors don't carry integer codes, so std assigns one to each error kind that a kernel
+// error can be converted to: the kind's index in `ERROR_KINDS` plus one, leaving 0 for "no error".
+// These are the codes reported by `os::errno`. Kernel error kinds are listed once here, so that
+// the table and the conversion can't disagree; anything else is uncategorized.
+macro_rules! error_kinds {
+    ($($kind:ident),* $(,)?) => {
+        const ERROR_KINDS: &[crate::io::ErrorKind] =
+            &[$(crate::io::ErrorKind::$kind,)* crate::io::ErrorKind::Uncategorized];
+
+        fn decode_core2_kind(kind: libtheseus::core2::io::ErrorKind) -> crate::io::ErrorKind {
+            match kind {
+                $(libtheseus::core2::io::ErrorKind::$kind => crate::io::ErrorKind::$kind,)*
+                _ => crate::io::ErrorKind::Uncategorized,
+            }
+        }
+    };
+}
+
+error_kinds![
+    NotFound,
+    PermissionDenied,
+    ConnectionRefused,
+    ConnectionReset,
+    ConnectionAborted,
+    NotConnected,
+    AddrInUse,
+    AddrNotAvailable,
+    BrokenPipe,
+    AlreadyExists,
+    WouldBlock,
+    InvalidInput,
+    InvalidData,
+    TimedOut,
+    WriteZero,
+    Interrupted,
+    Other,
+    UnexpectedEof,
+];
+
+fn error_code(kind: crate::io::ErrorKind) -> i32 {
+    ERROR_KINDS.iter().position(|k| *k == kind).map_or(0, |i| i as i32 + 1)
+}
+
+fn error_kind(code: i32) -> Option<crate::io::ErrorKind> {
+    let index = usize::try_from(code).ok()?.checked_sub(1)?;
+    ERROR_KINDS.get(index).copied()
 }
 
 impl From<libtheseus::core2::io::Error> for crate::io::Error {
     fn from(e: libtheseus::core2::io::Error) -> crate::io::Error {
-        use libtheseus::core2;
-
-        let kind = match e.kind() {
-            core2::io::ErrorKind::NotFound => crate::io::ErrorKind::NotFound,
-            core2::io::ErrorKind::PermissionDenied => crate::io::ErrorKind::PermissionDenied,
-            core2::io::ErrorKind::ConnectionRefused => crate::io::ErrorKind::ConnectionRefused,
-            core2::io::ErrorKind::ConnectionReset => crate::io::ErrorKind::ConnectionReset,
-            core2::io::ErrorKind::ConnectionAborted => crate::io::ErrorKind::ConnectionAborted,
-            core2::io::ErrorKind::NotConnected => crate::io::ErrorKind::NotConnected,
-            core2::io::ErrorKind::AddrInUse => crate::io::ErrorKind::AddrInUse,
-            core2::io::ErrorKind::AddrNotAvailable => crate::io::ErrorKind::AddrNotAvailable,
-            core2::io::ErrorKind::BrokenPipe => crate::io::ErrorKind::BrokenPipe,
-            core2::io::ErrorKind::AlreadyExists => crate::io::ErrorKind::AlreadyExists,
-            core2::io::ErrorKind::WouldBlock => crate::io::ErrorKind::WouldBlock,
-            core2::io::ErrorKind::InvalidInput => crate::io::ErrorKind::InvalidInput,
-            core2::io::ErrorKind::InvalidData => crate::io::ErrorKind::InvalidData,
-            core2::io::ErrorKind::TimedOut => crate::io::ErrorKind::TimedOut,
-            core2::io::ErrorKind::WriteZero => crate::io::ErrorKind::WriteZero,
-            core2::io::ErrorKind::Interrupted => crate::io::ErrorKind::Interrupted,
-            core2::io::ErrorKind::Other => crate::io::ErrorKind::Other,
-            core2::io::ErrorKind::UnexpectedEof => crate::io::ErrorKind::UnexpectedEof,
-            _ => crate::io::ErrorKind::Uncategorized,
-        };
+        let kind = decode_core2_kind(e.kind());
+        os::set_errno(error_code(kind));
 
         match e.into_inner() {
             Some(s) => crate::io::Error::new(kind, s),
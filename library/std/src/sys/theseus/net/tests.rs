@@ -0,0 +1,34 @@
+use super::LookupHost;
+use crate::io;
+use crate::net::{Ipv4Addr, Ipv6Addr, SocketAddr};
+
+#[test]
+fn lookup_localhost() {
+    for host in ["localhost:80", "LOCALHOST:80"] {
+        let lookup = LookupHost::try_from(host).unwrap();
+        assert_eq!(lookup.port(), 80, "{host}");
+        let addrs: Vec<SocketAddr> = lookup.collect();
+        assert_eq!(
+            addrs,
+            [
+                SocketAddr::new(Ipv4Addr::LOCALHOST.into(), 80),
+                SocketAddr::new(Ipv6Addr::LOCALHOST.into(), 80),
+            ],
+            "{host}"
+        );
+    }
+}
+
+#[test]
+fn lookup_other_host_is_unsupported() {
+    let err = LookupHost::try_from("example.com:80").err().unwrap();
+    assert_eq!(err.kind(), io::ErrorKind::Unsupported);
+}
+
+#[test]
+fn lookup_invalid_address() {
+    for host in ["localhost", "localhost:x"] {
+        let err = LookupHost::try_from(host).err().unwrap();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput, "{host}");
+    }
+}
@@ -1,18 +1,41 @@
-use super::{current_task, current_task_id, io_err};
+#[cfg(test)]
+mod tests;
+
+use super::{current_task, current_task_id};
 use crate::{
+    cell::Cell,
     error::Error as StdError,
     ffi::{OsStr, OsString},
     fmt, io,
-    marker::PhantomData,
     path::{self, PathBuf},
+    slice,
+    sys::os_str::Buf,
+    sys_common::{AsInner, FromInner},
 };
 
+thread_local! {
+    // Theseus returns errors as values instead of setting a global error number, so this holds
+    // the code of the last kernel error that was converted into an `io::Error` on this task.
+    static ERRNO: Cell<i32> = const { Cell::new(0) };
+}
+
+// Only conversions from kernel (core2) errors set this. Errors that std creates itself, such as
+// those from `stdio::stream_err`, `const_io_error!` or `unsupported()`, leave it untouched, so
+// after one of those this still returns the code of whatever kernel error came before.
 pub fn errno() -> i32 {
-    panic!("should not be used on this target");
+    ERRNO.try_with(|errno| errno.get()).unwrap_or(0)
+}
+
+pub(super) fn set_errno(code: i32) {
+    let _ = ERRNO.try_with(|errno| errno.set(code));
 }
 
-pub fn error_string(_errno: i32) -> String {
-    panic!("should not be used on this target");
+pub fn error_string(errno: i32) -> String {
+    match super::error_kind(errno) {
+        Some(kind) => kind.as_str().to_owned(),
+        None if errno == 0 => "no error".to_owned(),
+        None => format!("unknown error code {errno}"),
+    }
 }
 
 pub fn getcwd() -> io::Result<PathBuf> {
@@ -27,66 +50,91 @@ pub fn chdir(path: &path::Path) -> io::Result<()> {
         .chdir(&libtheseus::path::Path::new(
             path.to_str()
                 .ok_or_else(|| {
-                    io::Error::new(io::ErrorKind::InvalidData, "path was not valid unicode")
+                    io::const_io_error!(io::ErrorKind::InvalidData, "path was not valid unicode")
                 })?
                 .to_owned(),
         ))
         .map_err(|e| match e {
-            libtheseus::env::Error::NotADirectory => io::Error::new(
+            libtheseus::env::Error::NotADirectory => io::const_io_error!(
                 io::ErrorKind::NotADirectory,
                 "tried to change directory into node that isn't a directory",
             ),
-            libtheseus::env::Error::NotFound => io::Error::new(
+            libtheseus::env::Error::NotFound => io::const_io_error!(
                 io::ErrorKind::NotFound,
                 "tried to change directory into node that doesn't exist",
             ),
         })
 }
 
-pub struct SplitPaths<'a>(!, PhantomData<&'a ()>);
+const PATH_SEPARATOR: u8 = b':';
 
-pub fn split_paths(_unparsed: &OsStr) -> SplitPaths<'_> {
-    panic!("unsupported")
+pub struct SplitPaths<'a> {
+    iter: slice::Split<'a, u8, fn(&u8) -> bool>,
+}
+
+pub fn split_paths(unparsed: &OsStr) -> SplitPaths<'_> {
+    fn is_separator(b: &u8) -> bool {
+        *b == PATH_SEPARATOR
+    }
+    SplitPaths { iter: unparsed.as_inner().inner.split(is_separator as fn(&u8) -> bool) }
 }
 
 impl<'a> Iterator for SplitPaths<'a> {
     type Item = PathBuf;
     fn next(&mut self) -> Option<PathBuf> {
-        self.0
+        self.iter.next().map(|b| OsString::from_inner(Buf { inner: b.to_vec() }).into())
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
     }
 }
 
 #[derive(Debug)]
 pub struct JoinPathsError;
 
-pub fn join_paths<I, T>(_paths: I) -> Result<OsString, JoinPathsError>
+pub fn join_paths<I, T>(paths: I) -> Result<OsString, JoinPathsError>
 where
     I: Iterator<Item = T>,
     T: AsRef<OsStr>,
 {
-    // Theseus doesn't have the concept of a `PATH` environment variable.
-    Err(JoinPathsError)
+    let mut joined = Vec::new();
+
+    for (i, path) in paths.enumerate() {
+        let path = &path.as_ref().as_inner().inner;
+        if i > 0 {
+            joined.push(PATH_SEPARATOR)
+        }
+        if path.contains(&PATH_SEPARATOR) {
+            return Err(JoinPathsError);
+        }
+        joined.extend_from_slice(path);
+    }
+    Ok(OsString::from_inner(Buf { inner: joined }))
 }
 
 impl fmt::Display for JoinPathsError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        "not supported on this platform yet".fmt(f)
+        write!(f, "path segment contains separator `{}`", char::from(PATH_SEPARATOR))
     }
 }
 
 impl StdError for JoinPathsError {
     #[allow(deprecated)]
     fn description(&self) -> &str {
-        "not supported on this platform yet"
+        "failed to join paths"
     }
 }
 
 pub fn current_exe() -> io::Result<PathBuf> {
     let task = current_task()?;
-    let app_crate = task
-        .app_crate
-        .as_ref()
-        .ok_or_else(|| io_err("task didn't contain reference to app crate"))?;
+    // Tasks that weren't spawned from an application crate, e.g. when Rust code is called from a
+    // kernel crate, don't have an executable.
+    let app_crate = task.app_crate.as_ref().ok_or_else(|| {
+        io::const_io_error!(
+            io::ErrorKind::Unsupported,
+            "task didn't contain reference to app crate",
+        )
+    })?;
     let path = app_crate.lock_as_ref().object_file.lock().get_absolute_path();
     Ok(path.into())
 }
@@ -115,26 +163,32 @@ pub fn getenv(key: &OsStr) -> Option<OsString> {
 pub fn setenv(key: &OsStr, value: &OsStr) -> io::Result<()> {
     let task = current_task()?;
     task.get_env().lock().set(
-        key.to_str().ok_or_else(|| invalid_data_io_err("key was not valid unicode"))?.to_owned(),
-        value.to_str().ok_or_else(|| invalid_data_io_err("value was not valid unicode"))?.to_owned(),
+        key.to_str()
+            .ok_or(io::const_io_error!(io::ErrorKind::InvalidData, "key was not valid unicode"))?
+            .to_owned(),
+        value
+            .to_str()
+            .ok_or(io::const_io_error!(io::ErrorKind::InvalidData, "value was not valid unicode"))?
+            .to_owned(),
     );
     Ok(())
 }
 
 pub fn unsetenv(key: &OsStr) -> io::Result<()> {
     let task = current_task()?;
-    task.get_env()
-        .lock()
-        .unset(key.to_str().ok_or_else(|| invalid_data_io_err("key was not valid unicode"))?);
+    task.get_env().lock().unset(
+        key.to_str()
+            .ok_or(io::const_io_error!(io::ErrorKind::InvalidData, "key was not valid unicode"))?,
+    );
     Ok(())
 }
 
 pub fn temp_dir() -> PathBuf {
-    panic!("no filesystem on this platform")
+    getenv(OsStr::new("TMPDIR")).map(PathBuf::from).unwrap_or_else(|| PathBuf::from("/tmp"))
 }
 
 pub fn home_dir() -> Option<PathBuf> {
-    None
+    getenv(OsStr::new("HOME")).map(PathBuf::from)
 }
 
 pub fn exit(code: i32) -> ! {
@@ -148,7 +202,3 @@ pub fn exit(code: i32) -> ! {
 pub fn getpid() -> u32 {
     current_task_id().expect("couldn't get current task id") as u32
 }
-
-fn invalid_data_io_err(s: &str) -> io::Error {
-    io::Error::new(io::ErrorKind::InvalidData, s)
-}
@@ -0,0 +1,43 @@
+use super::{join_paths, setenv, split_paths};
+use crate::ffi::{OsStr, OsString};
+use crate::io;
+use crate::path::PathBuf;
+use crate::sys::os_str::Buf;
+use crate::sys_common::FromInner;
+
+#[test]
+fn split_join_round_trip() {
+    let cases: &[&[&str]] = &[
+        &["/bin"],
+        &["/bin", "/usr/bin"],
+        &["/bin", "", "/usr/bin"],
+        &["", "/bin", ""],
+        &["relative/dir", "/with spaces", "/with\\backslash"],
+    ];
+    for &paths in cases {
+        let joined = join_paths(paths.iter()).unwrap();
+        assert_eq!(joined, OsStr::new(&paths.join(":")), "{paths:?}");
+        let split: Vec<PathBuf> = split_paths(&joined).collect();
+        let expected: Vec<PathBuf> = paths.iter().map(PathBuf::from).collect();
+        assert_eq!(split, expected, "{paths:?}");
+    }
+}
+
+#[test]
+fn split_empty() {
+    let split: Vec<PathBuf> = split_paths(OsStr::new("")).collect();
+    assert_eq!(split, [PathBuf::new()]);
+}
+
+#[test]
+fn join_rejects_separator() {
+    assert!(join_paths(["/bin", "/usr:/bin"].iter()).is_err());
+    assert!(join_paths([":"].iter()).is_err());
+}
+
+#[test]
+fn setenv_rejects_non_utf8_key() {
+    let key = OsString::from_inner(Buf { inner: vec![b'K', 0xFF] });
+    let err = setenv(&key, OsStr::new("value")).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+}
@@ -1,3 +1,6 @@
+#[cfg(test)]
+mod tests;
+
 use crate::ffi::OsStr;
 use crate::fmt;
 use crate::io;
@@ -151,11 +154,11 @@ pub fn code(self) -> Option<NonZeroI32> {
 }
 
 #[derive(PartialEq, Eq, Clone, Copy, Debug)]
-pub struct ExitCode(bool);
+pub struct ExitCode(u8);
 
 impl ExitCode {
-    pub const SUCCESS: ExitCode = ExitCode(false);
-    pub const FAILURE: ExitCode = ExitCode(true);
+    pub const SUCCESS: ExitCode = ExitCode(0);
+    pub const FAILURE: ExitCode = ExitCode(1);
 
     pub fn as_i32(&self) -> i32 {
         self.0 as i32
@@ -164,10 +167,9 @@ pub fn as_i32(&self) -> i32 {
 
 impl From<u8> for ExitCode {
     fn from(code: u8) -> Self {
-        match code {
-            0 => Self::SUCCESS,
-            1..=255 => Self::FAILURE,
-        }
+        // Theseus tasks can exit with any value, so there's no need to collapse
+        // non-zero codes into `FAILURE`.
+        Self(code)
     }
 }
 
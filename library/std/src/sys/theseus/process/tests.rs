@@ -0,0 +1,10 @@
+use super::ExitCode;
+
+#[test]
+fn exit_code_values() {
+    assert_eq!(ExitCode::SUCCESS.as_i32(), 0);
+    assert_eq!(ExitCode::FAILURE.as_i32(), 1);
+    for code in [0, 1, 2, 42, 255] {
+        assert_eq!(ExitCode::from(code).as_i32(), i32::from(code));
+    }
+}
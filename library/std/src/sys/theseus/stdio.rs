@@ -1,10 +1,48 @@
-use super::io_err;
-use crate::io;
+#[cfg(test)]
+mod tests;
+
+use crate::{error::Error as StdError, fmt, io};
 use libtheseus::{
     core2::io::{Read, Write},
     stdio::{stderr, stdin, stdout},
 };
 
+// A task without a given stdio stream is the closest Theseus has to a closed file descriptor.
+// Those errors carry this payload so that `is_ebadf` can tell them apart from a `NotConnected`
+// error returned by a stream that does exist.
+#[derive(Debug)]
+struct MissingStream(String);
+
+impl fmt::Display for MissingStream {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl StdError for MissingStream {}
+
+fn stream_err(e: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::NotConnected, MissingStream(e.to_owned()))
+}
+
+// Writes as much of `buf` as the stream accepts. The caller holds the stream's lock throughout, so
+// writes from other tasks can't be interleaved with it. As `io::Write::write` only allows
+// returning an error if nothing was written, an error after a partial write is reported as a
+// short write instead; the caller will run into it again on its next call.
+fn write_locked<W: Write + ?Sized>(stream: &mut W, buf: &[u8]) -> io::Result<usize> {
+    let mut written = 0;
+    while written < buf.len() {
+        match stream.write(&buf[written..]) {
+            Ok(0) => break,
+            Ok(n) => written += n,
+            Err(e) if e.kind() == libtheseus::core2::io::ErrorKind::Interrupted => {}
+            Err(e) if written == 0 => return Err(io::Error::from(e)),
+            Err(_) => break,
+        }
+    }
+    Ok(written)
+}
+
 pub struct Stdin;
 pub struct Stdout;
 pub struct Stderr;
@@ -17,7 +55,7 @@ pub const fn new() -> Stdin {
 
 impl io::Read for Stdin {
     fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
-        let stdin = stdin().map_err(io_err)?;
+        let stdin = stdin().map_err(stream_err)?;
         let mut lock = stdin.lock();
         lock.read(buf).map_err(io::Error::from)
     }
@@ -31,13 +69,13 @@ pub const fn new() -> Stdout {
 
 impl io::Write for Stdout {
     fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
-        let stdout = stdout().map_err(io_err)?;
+        let stdout = stdout().map_err(stream_err)?;
         let mut lock = stdout.lock();
-        lock.write(buf).map_err(io::Error::from)
+        write_locked(&mut *lock, buf)
     }
 
     fn flush(&mut self) -> io::Result<()> {
-        let stdout = stdout().map_err(io_err)?;
+        let stdout = stdout().map_err(stream_err)?;
         let mut lock = stdout.lock();
         lock.flush().map_err(io::Error::from)
     }
@@ -51,24 +89,29 @@ pub const fn new() -> Stderr {
 
 impl io::Write for Stderr {
     fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
-        let stderr = stderr().map_err(io_err)?;
+        let stderr = stderr().map_err(stream_err)?;
         let mut lock = stderr.lock();
-        lock.write(buf).map_err(io::Error::from)
+        write_locked(&mut *lock, buf)
     }
 
     fn flush(&mut self) -> io::Result<()> {
-        let stderr = stderr().map_err(io_err)?;
+        let stderr = stderr().map_err(stream_err)?;
         let mut lock = stderr.lock();
         lock.flush().map_err(io::Error::from)
     }
 }
 
-pub const STDIN_BUF_SIZE: usize = 0;
+pub fn write_kernel_log(s: &str) -> io::Result<()> {
+    libtheseus::logger::write_str(s)
+        .map_err(|_| io::const_io_error!(io::ErrorKind::Other, "couldn't write to kernel log"))
+}
+
+pub const STDIN_BUF_SIZE: usize = crate::sys_common::io::DEFAULT_BUF_SIZE;
 
-pub fn is_ebadf(_err: &io::Error) -> bool {
-    true
+pub fn is_ebadf(err: &io::Error) -> bool {
+    err.get_ref().map_or(false, |e| e.is::<MissingStream>())
 }
 
-pub fn panic_output() -> Option<Vec<u8>> {
-    None
+pub fn panic_output() -> Option<impl io::Write> {
+    Some(Stderr::new())
 }
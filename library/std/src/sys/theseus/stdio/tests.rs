@@ -0,0 +1,21 @@
+use super::{is_ebadf, stream_err};
+use crate::io;
+use libtheseus::core2;
+
+#[test]
+fn missing_stream_is_ebadf() {
+    assert!(is_ebadf(&stream_err("no stdout stream")));
+}
+
+#[test]
+fn stream_errors_are_not_ebadf() {
+    for kind in [
+        core2::io::ErrorKind::NotConnected,
+        core2::io::ErrorKind::BrokenPipe,
+        core2::io::ErrorKind::Other,
+    ] {
+        let err = io::Error::from(core2::io::Error::from(kind));
+        assert!(!is_ebadf(&err), "{kind:?}");
+    }
+    assert!(!is_ebadf(&io::Error::new(io::ErrorKind::NotConnected, "not connected")));
+}
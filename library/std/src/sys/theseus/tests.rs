@@ -0,0 +1,26 @@
+use super::{decode_error_kind, error_code, error_kind, os, ERROR_KINDS};
+use crate::io;
+
+#[test]
+fn error_codes_round_trip() {
+    for &kind in ERROR_KINDS {
+        let code = error_code(kind);
+        assert_ne!(code, 0, "{kind:?}");
+        assert_eq!(error_kind(code), Some(kind), "{kind:?}");
+    }
+}
+
+#[test]
+fn invalid_error_codes() {
+    for code in [0, -1, ERROR_KINDS.len() as i32 + 1] {
+        assert_eq!(error_kind(code), None, "{code}");
+    }
+    assert_eq!(decode_error_kind(ERROR_KINDS.len() as i32 + 1), io::ErrorKind::Uncategorized);
+}
+
+#[test]
+fn error_strings() {
+    assert_eq!(os::error_string(0), "no error");
+    assert_eq!(os::error_string(error_code(io::ErrorKind::NotFound)), "entity not found");
+    assert_eq!(os::error_string(-1), "unknown error code -1");
+}
@@ -1,6 +1,6 @@
 use super::{current_task, current_task_id, io_err};
-use crate::{ffi::CStr, io, num::NonZeroUsize, sys::unsupported, time::Duration};
-use libtheseus::{mem, stdio, task};
+use crate::{cell::RefCell, cmp, ffi::CStr, io, num::NonZeroUsize, panic, time::Duration};
+use libtheseus::{cpu, mem, sleep, stdio, task};
 
 pub struct Thread(task::JoinableTaskRef);
 
@@ -9,19 +9,24 @@
 impl Thread {
     // unsafe: see thread::Builder::spawn_unchecked for safety requirements
     pub unsafe fn new(stack_size: usize, p: Box<dyn FnOnce()>) -> io::Result<Thread> {
-        let mmi_ref = mem::get_kernel_mmi_ref().ok_or_else(|| io_err("couldn't get kernel mmi"))?;
+        // Everything that can fail is done before the child task is spawned, so that an error
+        // never leaves behind a blocked task that will never be unblocked. If spawning itself
+        // fails, the stack is dropped along with the task builder.
+        let current_task_io_streams = stdio::get_streams(current_task_id()?).ok_or_else(|| {
+            io::const_io_error!(io::ErrorKind::Other, "couldn't get current task io streams")
+        })?;
+        let current_env = current_task()?.get_env();
+
+        let mmi_ref = mem::get_kernel_mmi_ref()
+            .ok_or_else(|| io::const_io_error!(io::ErrorKind::Other, "couldn't get kernel mmi"))?;
         let stack = task::alloc_stack_by_bytes(stack_size, &mut mmi_ref.lock().page_table)
-            .ok_or_else(|| io_err("couldn't allocate stack"))?;
+            .ok_or_else(|| io::const_io_error!(io::ErrorKind::Other, "couldn't allocate stack"))?;
 
         let child_task =
             task::new_task_builder(|_| p(), ()).block().stack(stack).spawn().map_err(io_err)?;
 
         // FIXME: We need to delete the streams when the thread exits.
-        let current_task_io_streams = stdio::get_streams(current_task_id()?)
-            .ok_or_else(|| io_err("couldn't get current task io streams"))?;
         stdio::insert_child_streams(child_task.id, current_task_io_streams);
-
-        let current_env = current_task()?.get_env();
         child_task.set_env(current_env);
 
         child_task.unblock();
@@ -32,14 +37,39 @@ pub fn yield_now() {
         task::yield_now();
     }
 
+    // `std::thread` calls this from the entry point of the spawned thread, before
+    // running any user code, so the current task is the child and not the parent
+    // that configured the name through `Builder::name`.
     pub fn set_name(name: &CStr) {
         let task = current_task().expect("couldn't get current task");
         let name = String::from_utf8_lossy(name.to_bytes()).to_string();
         task.set_name(name)
     }
 
-    pub fn sleep(_dur: Duration) {
-        panic!("can't sleep");
+    pub fn sleep(dur: Duration) {
+        if dur.is_zero() {
+            return Thread::yield_now();
+        }
+
+        // The kernel converts the duration to timer ticks and adds it to the current tick
+        // count, and the tick rate depends on the platform's timer. A day is far below what
+        // that can overflow at any realistic tick rate, so very long sleeps are split into
+        // steps of at most a day.
+        const MAX_STEP: Duration = Duration::from_secs(24 * 60 * 60);
+        let mut remaining = dur;
+        while !remaining.is_zero() {
+            let step = cmp::min(remaining, MAX_STEP);
+            // This blocks the task until the step has elapsed, so other tasks run in the
+            // meantime. The kernel only fails to do so if the current task can't be blocked,
+            // which can't happen for the running task unless the scheduler's state is
+            // corrupted, so there is nothing sensible to recover to.
+            sleep::sleep(step).expect("failed to sleep");
+            remaining -= step;
+        }
+    }
+
+    pub fn has_exited(&self) -> bool {
+        self.0.has_exited()
     }
 
     pub fn join(self) {
@@ -48,7 +78,45 @@ pub fn join(self) {
 }
 
 pub fn available_parallelism() -> io::Result<NonZeroUsize> {
-    unsupported()
+    NonZeroUsize::new(cpu::cpu_count() as usize)
+        .ok_or_else(|| io::const_io_error!(io::ErrorKind::Other, "couldn't get cpu count"))
+}
+
+pub fn affinity_parallelism() -> io::Result<NonZeroUsize> {
+    match current_task()?.pinned_core() {
+        Some(_) => Ok(NonZeroUsize::new(1).unwrap()),
+        None => available_parallelism(),
+    }
+}
+
+pub fn current_cpu() -> Option<usize> {
+    Some(u32::from(cpu::current_cpu()) as usize)
+}
+
+pub struct NoPreemptGuard(task::PreemptionGuard);
+
+pub fn hold_preemption() -> NoPreemptGuard {
+    NoPreemptGuard(task::hold_preemption())
+}
+
+struct ExitCallbacks(RefCell<Vec<Box<dyn FnOnce()>>>);
+
+impl Drop for ExitCallbacks {
+    fn drop(&mut self) {
+        while let Some(callback) = self.0.get_mut().pop() {
+            // The panic has already been reported by the panic hook, and there is no one to
+            // propagate it to, so just move on to the next callback.
+            let _ = panic::catch_unwind(panic::AssertUnwindSafe(callback));
+        }
+    }
+}
+
+thread_local! {
+    static EXIT_CALLBACKS: ExitCallbacks = ExitCallbacks(RefCell::new(Vec::new()));
+}
+
+pub fn at_exit(callback: Box<dyn FnOnce()>) {
+    EXIT_CALLBACKS.with(|callbacks| callbacks.0.borrow_mut().push(callback));
 }
 
 pub mod guard {
@@ -1,4 +1,5 @@
 use crate::time::Duration;
+use libtheseus::time;
 
 #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Debug, Hash)]
 pub struct Instant(Duration);
@@ -10,7 +11,8 @@
 
 impl Instant {
     pub fn now() -> Instant {
-        panic!("time not implemented on this platform")
+        let now = time::now::<time::Monotonic>();
+        Instant(now.duration_since(time::Instant::ZERO))
     }
 
     pub fn checked_sub_instant(&self, other: &Instant) -> Option<Duration> {
@@ -28,7 +30,8 @@ pub fn checked_sub_duration(&self, other: &Duration) -> Option<Instant> {
 
 impl SystemTime {
     pub fn now() -> SystemTime {
-        panic!("time not implemented on this platform")
+        // The wall clock reports the time elapsed since the Unix epoch.
+        SystemTime(time::now::<time::WallTime>())
     }
 
     pub fn sub_time(&self, other: &SystemTime) -> Result<Duration, Duration> {